@@ -3,13 +3,16 @@
 //! This module exposes the type [`Component`] which is a wrapper around an
 //! HTML element. It can be used to create reusable components.
 
-use core::cell::UnsafeCell;
+use core::any::Any;
+use core::cell::{RefCell, UnsafeCell};
+use core::hash::Hash;
 use core::sync::atomic::{AtomicBool, Ordering::SeqCst};
 
 use alloc::boxed::Box;
 use alloc::rc::{Rc, Weak};
-use alloc::string::ToString;
+use alloc::string::{String, ToString};
 use alloc::vec::Vec;
+use hashbrown::HashMap;
 use wasm_bindgen::JsCast;
 use wasm_bindgen::prelude::Closure;
 use web_sys::HtmlElement;
@@ -22,7 +25,24 @@ use crate::store::StoreUnsubscriber;
 enum Dependency {
     Children(Component),
     Closure(Closure<dyn FnMut()>),
+    EventClosure(Closure<dyn FnMut(web_sys::Event)>),
     Subscription(StoreUnsubscriber),
+    // Opaque state owned by a builder method (e.g. `with_each`'s mounted
+    // list), kept alive for as long as the parent is, regardless of how
+    // long the subscription driving it lives.
+    List(Box<dyn Any>),
+    // A closure registered on `window` rather than on the component's own
+    // element: unlike an element, `window` is never detached/GC'd together
+    // with the component, so its listener has to be removed explicitly.
+    WindowListener(&'static str, Closure<dyn FnMut()>),
+}
+
+// The reconciled state backing `Component::with_each`. Owned by the parent
+// via `Dependency::List` so the mounted children (and their subscriptions)
+// survive even if `items` only calls back once, e.g. a `Value<Vec<T>>`.
+struct EachList<K> {
+    mounted: Vec<(K, Component)>,
+    index: HashMap<K, usize>,
 }
 
 impl Drop for Dependency {
@@ -31,6 +51,11 @@ impl Drop for Dependency {
     fn drop(&mut self) {
         match self {
             Self::Subscription(subscription) => subscription.unsubscribe(),
+            Self::WindowListener(event, listener) => {
+                web_sys::window().unwrap()
+                    .remove_event_listener_with_callback(event, listener.as_ref().unchecked_ref())
+                    .ok();
+            },
             _ => (),
         }
     }
@@ -85,12 +110,28 @@ impl Component {
         self.push_dep(Dependency::Closure(closure));
     }
 
+    // Push an event closure to the component's storage.
+    #[inline]
+    fn push_event_closure(&self, closure: Closure<dyn FnMut(web_sys::Event)>) {
+        self.push_dep(Dependency::EventClosure(closure));
+    }
+
     // Adds an unubsription to the component, to be performed when it is dropped.
     #[inline]
     fn push_unsub(&self, unsub: StoreUnsubscriber) {
         self.push_dep(Dependency::Subscription(unsub));
     }
 
+    // Registers `listener` on `window` for `event`, removing it when this
+    // component is dropped.
+    #[inline]
+    fn push_window_listener(&self, event: &'static str, listener: Closure<dyn FnMut()>) {
+        web_sys::window().unwrap()
+            .add_event_listener_with_callback(event, listener.as_ref().unchecked_ref())
+            .unwrap();
+        self.push_dep(Dependency::WindowListener(event, listener));
+    }
+
     // Sets the inner html attribute of the element on store update.
     #[inline]
     pub(crate) fn set_inner_html<S: ToString>(&self, text: impl Subscribable<S>) {
@@ -112,6 +153,64 @@ impl Component {
         self.push_closure(on_click);
     }
 
+    /// Registers `handler` on the given DOM event, downcasting the generic
+    /// [`web_sys::Event`] to the concrete event type `E` before calling it.
+    /// This method is meant to be chained.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use wasmide::prelude::*;
+    /// html::div(Style::NONE)
+    ///     .on::<web_sys::MouseEvent>("mouseover", |event| {
+    ///         let _ = event;
+    ///     });
+    /// ```
+    #[inline]
+    pub fn on<E: JsCast + 'static>(
+        self,
+        event: &'static str,
+        mut handler: impl FnMut(E) + 'static,
+    ) -> Self {
+        let closure = Closure::wrap(Box::new(move |event: web_sys::Event| {
+            if let Ok(event) = event.dyn_into::<E>() {
+                handler(event);
+            }
+        }) as Box<dyn FnMut(web_sys::Event)>);
+
+        self.html()
+            .add_event_listener_with_callback(event, closure.as_ref().unchecked_ref())
+            .unwrap();
+        self.push_event_closure(closure);
+        self
+    }
+
+    /// Registers `handler` to run with the current value of the underlying
+    /// `<input>`/`<textarea>` element every time it changes.
+    /// This method is meant to be chained.
+    #[inline]
+    pub fn on_input(self, mut handler: impl FnMut(String) + 'static) -> Self {
+        self.on::<web_sys::InputEvent>("input", move |event| {
+            let Some(target) = event.target() else { return };
+
+            if let Ok(input) = target.clone().dyn_into::<web_sys::HtmlInputElement>() {
+                handler(input.value());
+            } else if let Ok(textarea) = target.dyn_into::<web_sys::HtmlTextAreaElement>() {
+                handler(textarea.value());
+            }
+        })
+    }
+
+    /// Registers `handler` to run with the key (`KeyboardEvent::key`) of
+    /// every keystroke.
+    /// This method is meant to be chained.
+    #[inline]
+    pub fn on_key(self, mut handler: impl FnMut(String) + 'static) -> Self {
+        self.on::<web_sys::KeyboardEvent>("keydown", move |event| {
+            handler(event.key());
+        })
+    }
+
     // Creates a new component with the given html tag_name and style.
     #[inline]
     pub(crate) fn new(tag_name: &'static str, style: Style) -> Self {
@@ -201,9 +300,13 @@ impl Component {
     /// 
     /// This will in fact only set the class attribute of the html element
     /// to the string wrapped in the given style.
-    /// 
+    ///
+    /// The class string is interned (see [`Style::interned`]) before being
+    /// handed to the DOM, so setting the same style on many components only
+    /// crosses the JS/Rust boundary once.
+    ///
     /// # Examples
-    /// 
+    ///
     /// ```no_run
     /// # use wasmide::prelude::*;
     /// let root = Component::root(Style::NONE);
@@ -211,7 +314,7 @@ impl Component {
     /// ```
     #[inline]
     pub fn set_style(&self, style: Style) {
-        self.html().set_class_name(style.0);
+        self.html().set_class_name(crate::style::intern(style.0));
     }
 
     /// Appends a child to the component. This method is meant to be chained.
@@ -318,9 +421,250 @@ impl Component {
         self.push_unsub(unsub);
         self
     }
+
+    /// Appends a dynamically-sized, store-backed list of children, reusing
+    /// and reordering components as `items` changes instead of rebuilding
+    /// the whole list.
+    /// This method is meant to be chained.
+    ///
+    /// Each item is identified by the key returned by `key`. As long as an
+    /// item's key stays present across updates, its [`Component`] (built
+    /// once, by `view`) is kept alive and simply moved to its new position,
+    /// so its subscriptions and local state survive. Items whose key
+    /// disappears are removed from the DOM and dropped; new keys are built
+    /// lazily via `view`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use wasmide::prelude::*;
+    /// let names = Store::new(vec!["Alice", "Bob"]);
+    ///
+    /// Component::root(Style::NONE)
+    ///     .with_each(
+    ///         names,
+    ///         |name| *name,
+    ///         |name| html::p(Value(*name), Style::NONE),
+    ///     );
+    /// ```
+    #[inline]
+    pub fn with_each<T: 'static, K: Eq + Hash + Clone + 'static>(
+        self,
+        items: impl Subscribable<Vec<T>>,
+        key: impl Fn(&T) -> K + 'static,
+        view: impl Fn(&T) -> Component + 'static,
+    ) -> Self {
+        let this = self.downgrade();
+        let state: Rc<RefCell<EachList<K>>> = Rc::new(RefCell::new(EachList {
+            mounted: Vec::new(),
+            index: HashMap::new(),
+        }));
+
+        // Owned by the parent, so the mounted children (and their own
+        // subscriptions/closures) survive even if `items` only calls back
+        // once, e.g. a `Value<Vec<T>>`.
+        self.push_dep(Dependency::List(Box::new(state.clone())));
+
+        let for_subscribe = state.clone();
+        let unsub = items.subscribe(move |items| {
+            let Some(parent) = this.upgrade() else { return };
+            let mut state = for_subscribe.borrow_mut();
+
+            let mut next_mounted: Vec<(K, Component)> = Vec::with_capacity(items.len());
+            let mut next_index: HashMap<K, usize> = HashMap::with_capacity(items.len());
+
+            for item in items {
+                let k = key(item);
+                let comp = match state.index.get(&k) {
+                    Some(&i) => state.mounted[i].1.clone(),
+                    None => {
+                        let comp = view(item);
+                        parent.html().append_child(comp.html()).unwrap();
+                        comp
+                    },
+                };
+                next_index.insert(k.clone(), next_mounted.len());
+                next_mounted.push((k, comp));
+            }
+
+            // Detach and drop the components whose key is no longer present.
+            for (k, comp) in state.mounted.drain(..) {
+                if !next_index.contains_key(&k) {
+                    parent.html().remove_child(comp.html()).ok();
+                }
+            }
+
+            // Reorder the DOM to match the new order, walking the sequence
+            // front-to-back and moving only the nodes that are not already
+            // in the right place.
+            let mut cursor = parent.html().first_child();
+            for (_, comp) in next_mounted.iter() {
+                let node: &web_sys::Node = comp.html().as_ref();
+                if let Some(current) = &cursor {
+                    if current.is_same_node(Some(node)) {
+                        cursor = current.next_sibling();
+                        continue;
+                    }
+                }
+                parent.html().insert_before(node, cursor.as_ref()).unwrap();
+            }
+
+            state.mounted = next_mounted;
+            state.index = next_index;
+        });
+
+        self.push_unsub(unsub);
+        self
+    }
+
+    /// Appends a child that is swapped based on `window.location.hash`, the
+    /// way rust-dominator's `Route::from_url` does.
+    /// This method is meant to be chained.
+    ///
+    /// `routes` is called with a hash (without its leading `#`) the first
+    /// time that hash is visited, and must return the [`Component`] to show
+    /// for it. Components are built lazily that way and then just
+    /// hidden/shown on further visits, the same way [`Component::with_if`]
+    /// hides its child.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use wasmide::prelude::*;
+    /// Component::root(Style::NONE)
+    ///     .with_route(|hash| match hash {
+    ///         "/about" => html::p(Value("About"), Style::NONE),
+    ///         _ => html::p(Value("Home"), Style::NONE),
+    ///     });
+    /// ```
+    #[inline]
+    pub fn with_route(self, routes: impl Fn(&str) -> Component + 'static) -> Self {
+        let route = Store::new(crate::router::current_hash());
+
+        let on_hash_change = route.clone();
+        let listener = Closure::wrap(Box::new(move || {
+            on_hash_change.set(crate::router::current_hash());
+        }) as Box<dyn FnMut()>);
+        self.push_window_listener("hashchange", listener);
+
+        let this = self.downgrade();
+        let routes = Rc::new(routes);
+        let mut shown: Option<String> = None;
+        let mut cache: HashMap<String, Children<Box<dyn FnOnce() -> Component>>> = HashMap::new();
+
+        let unsub = route.subscribe(move |hash: &String| {
+            if shown.as_ref() == Some(hash) {
+                return;
+            }
+
+            if let Some(shown_hash) = shown.take() {
+                cache.get(&shown_hash).unwrap().deactivate();
+            }
+
+            let routes = routes.clone();
+            let hash_for_view = hash.clone();
+            cache.entry(hash.clone())
+                .or_insert_with(|| Children::new(Box::new(move || routes(&hash_for_view)) as Box<dyn FnOnce() -> Component>))
+                .activate(&this);
+
+            shown = Some(hash.clone());
+        });
+
+        self.push_unsub(unsub);
+        self
+    }
+
+    /// Wires a form element to a [`Store`] in both directions: the element
+    /// is updated whenever `store` changes, and `store` is updated with the
+    /// element's new value whenever the user edits it.
+    /// This method is meant to be chained.
+    ///
+    /// Implemented for `String` (tracking `.value` on
+    /// [`html::input`](crate::html::input)/[`html::textarea`](crate::html::textarea))
+    /// and `bool` (tracking `.checked` on [`html::checkbox`](crate::html::checkbox)).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use wasmide::prelude::*;
+    /// let name = Store::new(String::new());
+    ///
+    /// Component::root(Style::NONE)
+    ///     .with(html::input(Style::NONE).bind(name));
+    /// ```
+    #[inline]
+    pub fn bind<T: Bindable>(self, store: Store<T>) -> Self {
+        let weak = self.downgrade();
+
+        let for_write = weak.clone();
+        let unsub = store.subscribe(move |value| {
+            if let Some(comp) = for_write.upgrade() {
+                T::write(comp.html(), value);
+            }
+        });
+        self.push_unsub(unsub);
+
+        let for_read = weak;
+        self.on::<web_sys::Event>(T::EVENT, move |_event| {
+            if let Some(comp) = for_read.upgrade() {
+                if let Some(value) = T::read(comp.html()) {
+                    store.set(value);
+                }
+            }
+        })
+    }
+}
+
+/// A value that a form element managed by [`Component::bind`] can be read
+/// from and written to.
+pub trait Bindable: Sized + 'static {
+    #[doc(hidden)]
+    const EVENT: &'static str;
+    #[doc(hidden)]
+    fn read(element: &HtmlElement) -> Option<Self>;
+    #[doc(hidden)]
+    fn write(element: &HtmlElement, value: &Self);
+}
+
+impl Bindable for String {
+    const EVENT: &'static str = "input";
+
+    #[inline]
+    fn read(element: &HtmlElement) -> Option<Self> {
+        if let Some(input) = element.dyn_ref::<web_sys::HtmlInputElement>() {
+            Some(input.value())
+        } else {
+            element.dyn_ref::<web_sys::HtmlTextAreaElement>().map(|textarea| textarea.value())
+        }
+    }
+
+    #[inline]
+    fn write(element: &HtmlElement, value: &Self) {
+        if let Some(input) = element.dyn_ref::<web_sys::HtmlInputElement>() {
+            input.set_value(value);
+        } else if let Some(textarea) = element.dyn_ref::<web_sys::HtmlTextAreaElement>() {
+            textarea.set_value(value);
+        }
+    }
+}
+
+impl Bindable for bool {
+    const EVENT: &'static str = "change";
+
+    #[inline]
+    fn read(element: &HtmlElement) -> Option<Self> {
+        element.dyn_ref::<web_sys::HtmlInputElement>().map(|input| input.checked())
+    }
+
+    #[inline]
+    fn write(element: &HtmlElement, value: &Self) {
+        if let Some(input) = element.dyn_ref::<web_sys::HtmlInputElement>() {
+            input.set_checked(*value);
+        }
+    }
 }
 
-// An enum representing a lazy-initialized component, that is to be 
+// An enum representing a lazy-initialized component, that is to be
 // attached to a parent when crated, and hidden when deactivated.
 enum Children<F: FnOnce() -> Component> {
     Uninit(Option<F>),
@@ -0,0 +1,25 @@
+//! Error types shared across the crate.
+
+use alloc::string::String;
+use core::fmt;
+
+/// The error type returned by fallible wasmide operations.
+#[derive(Debug)]
+pub enum Error {
+    /// A `serde_json` (de)serialization failed.
+    Serde(String),
+    /// A JavaScript API call returned an unexpected value.
+    Js(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Serde(msg) => write!(f, "serialization error: {msg}"),
+            Self::Js(msg) => write!(f, "javascript error: {msg}"),
+        }
+    }
+}
+
+/// A convenience alias for `Result<T, Error>`.
+pub type Result<T> = core::result::Result<T, Error>;
@@ -0,0 +1,71 @@
+//! Constructors for common html elements.
+
+use alloc::string::ToString;
+
+use crate::component::Component;
+use crate::store::Subscribable;
+use crate::style::Style;
+
+/// Creates a `<div>` component.
+#[inline]
+pub fn div(style: Style) -> Component {
+    Component::new("div", style)
+}
+
+/// Creates a `<span>` component whose text content tracks `text`.
+#[inline]
+pub fn span<S: ToString>(text: impl Subscribable<S>, style: Style) -> Component {
+    let comp = Component::new("span", style);
+    comp.set_inner_html(text);
+    comp
+}
+
+/// Creates a `<p>` component whose text content tracks `text`.
+#[inline]
+pub fn p<S: ToString>(text: impl Subscribable<S>, style: Style) -> Component {
+    let comp = Component::new("p", style);
+    comp.set_inner_html(text);
+    comp
+}
+
+/// Creates a `<button>` component whose text content tracks `text`, calling
+/// `on_click` every time it is clicked.
+#[inline]
+pub fn button<S: ToString>(
+    text: impl Subscribable<S>,
+    style: Style,
+    on_click: impl FnMut() + 'static,
+) -> Component {
+    let comp = Component::new("button", style);
+    comp.set_inner_html(text);
+    comp.set_on_click(on_click);
+    comp
+}
+
+/// Creates an `<input type="text">` component, to be paired with
+/// [`Component::bind`](crate::component::Component::bind) to track its value
+/// in a [`Store`](crate::store::Store)`<String>`.
+#[inline]
+pub fn input(style: Style) -> Component {
+    let comp = Component::new("input", style);
+    comp.html().set_attribute("type", "text").unwrap();
+    comp
+}
+
+/// Creates a `<textarea>` component, to be paired with
+/// [`Component::bind`](crate::component::Component::bind) to track its value
+/// in a [`Store`](crate::store::Store)`<String>`.
+#[inline]
+pub fn textarea(style: Style) -> Component {
+    Component::new("textarea", style)
+}
+
+/// Creates an `<input type="checkbox">` component, to be paired with
+/// [`Component::bind`](crate::component::Component::bind) to track whether
+/// it is checked in a [`Store`](crate::store::Store)`<bool>`.
+#[inline]
+pub fn checkbox(style: Style) -> Component {
+    let comp = Component::new("input", style);
+    comp.html().set_attribute("type", "checkbox").unwrap();
+    comp
+}
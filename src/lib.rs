@@ -7,6 +7,7 @@ extern crate alloc;
 pub mod component;
 pub mod error;
 pub mod html;
+pub mod router;
 pub mod store;
 pub mod style;
 
@@ -15,6 +16,9 @@ pub mod prelude {
 
     pub use crate::component::Component;
     pub use crate::html;
-    pub use crate::store::{Store, Subscribable, Value};
+    pub use crate::router;
+    pub use crate::store::{
+        AsyncState, Combine, Dedup, Filter, Map, Store, Subscribable, Value, fetch,
+    };
     pub use crate::style::Style;
 }
\ No newline at end of file
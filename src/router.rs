@@ -0,0 +1,35 @@
+//! Hash-based client-side routing.
+//!
+//! Lets a [`Component`](crate::component::Component) swap its child based on
+//! `window.location.hash`, giving wasmide SPA navigation without leaving the
+//! declarative component API. See [`Component::with_route`](crate::component::Component::with_route).
+
+use alloc::string::{String, ToString};
+
+// Reads the current `location.hash`, stripped of its leading `#`.
+pub(crate) fn current_hash() -> String {
+    web_sys::window().unwrap()
+        .location()
+        .hash().unwrap_or_default()
+        .trim_start_matches('#')
+        .to_string()
+}
+
+/// Navigates to `hash`, updating `window.location.hash`.
+///
+/// This fires a `hashchange` event, which every
+/// [`Component::with_route`](crate::component::Component::with_route) listens
+/// for in order to re-render its routed child.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use wasmide::prelude::*;
+/// router::navigate("/about");
+/// ```
+#[inline]
+pub fn navigate(hash: &str) {
+    web_sys::window().unwrap()
+        .location()
+        .set_hash(hash).unwrap();
+}
@@ -0,0 +1,518 @@
+//! Reactive stores, the subscription mechanism [`Component`](crate::component::Component)
+//! uses to react to data changes.
+
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::cell::RefCell;
+use core::future::Future;
+use core::marker::PhantomData;
+
+use crate::error::{Error, Result};
+
+// The shared state of a store: a value plus the subscribers watching it.
+struct Inner<T> {
+    value: T,
+    next_id: u64,
+    subscribers: Vec<(u64, Box<dyn FnMut(&T)>)>,
+    // Keeps alive subscriptions the store holds onto itself, e.g. the
+    // write-back subscription of `Store::persistent`.
+    keep_alive: Vec<StoreUnsubscriber>,
+}
+
+impl<T> Inner<T> {
+    #[inline]
+    fn notify(&mut self) {
+        for (_, subscriber) in self.subscribers.iter_mut() {
+            subscriber(&self.value);
+        }
+    }
+}
+
+/// A handle returned by [`Subscribable::subscribe`] that stops the
+/// subscription when [`unsubscribe`](StoreUnsubscriber::unsubscribe) is
+/// called.
+///
+/// Type-erased so it can be stored uniformly regardless of what kind of
+/// store it came from, e.g. in `Dependency::Subscription`.
+pub struct StoreUnsubscriber(Box<dyn FnMut()>);
+
+impl StoreUnsubscriber {
+    #[inline]
+    fn new(f: impl FnMut() + 'static) -> Self {
+        Self(Box::new(f))
+    }
+
+    /// Stops the subscription. Idempotent: calling it more than once is a no-op.
+    #[inline]
+    pub fn unsubscribe(&mut self) {
+        (self.0)();
+    }
+}
+
+/// A source of values of type `T` that a [`Component`](crate::component::Component)
+/// can react to.
+///
+/// Implemented by [`Store`] and by every combinator derived from it
+/// ([`Store::compose`], [`Subscribable::map`], [`Subscribable::filter`],
+/// [`Subscribable::dedup`], [`Store::combine`], ...), as well as by
+/// [`Value`] for constants.
+pub trait Subscribable<T> {
+    /// Calls `f` once with the current value, then again every time the
+    /// value changes, until the returned [`StoreUnsubscriber`] is used to
+    /// unsubscribe.
+    fn subscribe(&self, f: impl FnMut(&T) + 'static) -> StoreUnsubscriber;
+
+    /// Derives a read-only [`Subscribable`] that maps every value through `f`.
+    #[inline]
+    fn map<U: 'static>(self, f: impl Fn(&T) -> U + 'static) -> Map<Self, T, U>
+    where
+        Self: Clone + Sized + 'static,
+        T: 'static,
+    {
+        Map {
+            source: self,
+            f: Rc::new(f),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Derives a read-only [`Subscribable`] that only notifies subscribers
+    /// when `predicate` holds for the new value, dropping the update
+    /// otherwise.
+    #[inline]
+    fn filter(self, predicate: impl Fn(&T) -> bool + 'static) -> Filter<Self, T>
+    where
+        Self: Clone + Sized + 'static,
+        T: 'static,
+    {
+        Filter {
+            source: self,
+            predicate: Rc::new(predicate),
+        }
+    }
+
+    /// Derives a read-only [`Subscribable`] that only notifies subscribers
+    /// when the new value differs from the last one it saw.
+    #[inline]
+    fn dedup(self) -> Dedup<Self, T>
+    where
+        Self: Clone + Sized + 'static,
+        T: PartialEq + Clone + 'static,
+    {
+        Dedup {
+            source: self,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// A reactive container for a value of type `T`.
+///
+/// Cloning a [`Store`] is cheap and yields another handle to the same
+/// underlying value; writing through any handle notifies every subscriber,
+/// including ones registered through other handles.
+pub struct Store<T>(Rc<RefCell<Inner<T>>>);
+
+impl<T> Clone for Store<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Store(self.0.clone())
+    }
+}
+
+impl<T: 'static> Store<T> {
+    /// Creates a new store with the given initial value.
+    #[inline]
+    pub fn new(value: T) -> Self {
+        Store(Rc::new(RefCell::new(Inner {
+            value,
+            next_id: 0,
+            subscribers: Vec::new(),
+            keep_alive: Vec::new(),
+        })))
+    }
+
+    /// Replaces the stored value, notifying every subscriber.
+    #[inline]
+    pub fn set(&self, value: T) {
+        let mut inner = self.0.borrow_mut();
+        inner.value = value;
+        inner.notify();
+    }
+
+    /// Updates the stored value in place, notifying every subscriber.
+    #[inline]
+    pub fn update(&self, f: impl FnOnce(&mut T)) {
+        let mut inner = self.0.borrow_mut();
+        f(&mut inner.value);
+        inner.notify();
+    }
+
+    /// Derives a read-only [`Subscribable`] that maps every value of this
+    /// store through `f`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use wasmide::prelude::*;
+    /// let store = Store::new(42);
+    /// let greater_than_42 = store.compose(|n| *n > 42);
+    /// ```
+    #[inline]
+    pub fn compose<U: 'static>(&self, f: impl Fn(&T) -> U + 'static) -> Compose<T, U> {
+        Compose {
+            source: self.clone(),
+            f: Rc::new(f),
+        }
+    }
+}
+
+impl<T: 'static> Subscribable<T> for Store<T> {
+    #[inline]
+    fn subscribe(&self, mut f: impl FnMut(&T) + 'static) -> StoreUnsubscriber {
+        let mut inner = self.0.borrow_mut();
+        f(&inner.value);
+
+        let id = inner.next_id;
+        inner.next_id += 1;
+        inner.subscribers.push((id, Box::new(f)));
+        drop(inner);
+
+        let weak = Rc::downgrade(&self.0);
+        StoreUnsubscriber::new(move || {
+            if let Some(inner) = weak.upgrade() {
+                inner.borrow_mut().subscribers.retain(|(sub_id, _)| *sub_id != id);
+            }
+        })
+    }
+}
+
+/// A [`Subscribable`] derived from a [`Store`] by mapping its values through
+/// a function. Created by [`Store::compose`].
+pub struct Compose<T, U> {
+    source: Store<T>,
+    f: Rc<dyn Fn(&T) -> U>,
+}
+
+impl<T, U> Clone for Compose<T, U> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Compose {
+            source: self.source.clone(),
+            f: self.f.clone(),
+        }
+    }
+}
+
+impl<T: 'static, U: 'static> Subscribable<U> for Compose<T, U> {
+    #[inline]
+    fn subscribe(&self, mut g: impl FnMut(&U) + 'static) -> StoreUnsubscriber {
+        let f = self.f.clone();
+        self.source.subscribe(move |value| g(&f(value)))
+    }
+}
+
+/// A [`Subscribable`] derived by mapping another one's values through a
+/// function. Created by [`Subscribable::map`].
+pub struct Map<S, T, U> {
+    source: S,
+    f: Rc<dyn Fn(&T) -> U>,
+    _marker: PhantomData<T>,
+}
+
+impl<S: Clone, T, U> Clone for Map<S, T, U> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Map {
+            source: self.source.clone(),
+            f: self.f.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<S: Subscribable<T> + 'static, T: 'static, U: 'static> Subscribable<U> for Map<S, T, U> {
+    #[inline]
+    fn subscribe(&self, mut g: impl FnMut(&U) + 'static) -> StoreUnsubscriber {
+        let f = self.f.clone();
+        self.source.subscribe(move |value| g(&f(value)))
+    }
+}
+
+/// A [`Subscribable`] that only forwards values for which a predicate holds.
+/// Created by [`Subscribable::filter`].
+pub struct Filter<S, T> {
+    source: S,
+    predicate: Rc<dyn Fn(&T) -> bool>,
+}
+
+impl<S: Clone, T> Clone for Filter<S, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Filter {
+            source: self.source.clone(),
+            predicate: self.predicate.clone(),
+        }
+    }
+}
+
+impl<S: Subscribable<T> + 'static, T: 'static> Subscribable<T> for Filter<S, T> {
+    #[inline]
+    fn subscribe(&self, mut g: impl FnMut(&T) + 'static) -> StoreUnsubscriber {
+        let predicate = self.predicate.clone();
+        self.source.subscribe(move |value| {
+            if predicate(value) {
+                g(value);
+            }
+        })
+    }
+}
+
+/// A [`Subscribable`] that only forwards a value when it differs from the
+/// last one seen. Created by [`Subscribable::dedup`].
+pub struct Dedup<S, T> {
+    source: S,
+    _marker: PhantomData<T>,
+}
+
+impl<S: Clone, T> Clone for Dedup<S, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Dedup {
+            source: self.source.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<S: Subscribable<T> + 'static, T: PartialEq + Clone + 'static> Subscribable<T> for Dedup<S, T> {
+    #[inline]
+    fn subscribe(&self, mut g: impl FnMut(&T) + 'static) -> StoreUnsubscriber {
+        let last: Rc<RefCell<Option<T>>> = Rc::new(RefCell::new(None));
+        self.source.subscribe(move |value| {
+            let changed = last.borrow().as_ref() != Some(value);
+            if changed {
+                *last.borrow_mut() = Some(value.clone());
+                g(value);
+            }
+        })
+    }
+}
+
+/// A read-only [`Subscribable`] recomputed from two other sources every time
+/// either of them changes. Created by [`Store::combine`].
+///
+/// Until both sources have produced at least one value (e.g. because one of
+/// them is a [`Subscribable::filter`] that hasn't matched its predicate
+/// yet), a `Combine` simply has no value, and does not call back
+/// subscribers before it does.
+pub struct Combine<T> {
+    // `None` until both sources have produced a value at least once.
+    store: Store<Option<T>>,
+    // Keeps the subscriptions to the two combined sources alive for as long
+    // as this combinator is.
+    _keep_alive: Rc<(StoreUnsubscriber, StoreUnsubscriber)>,
+}
+
+impl<T> Clone for Combine<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Combine {
+            store: self.store.clone(),
+            _keep_alive: self._keep_alive.clone(),
+        }
+    }
+}
+
+impl<T: 'static> Subscribable<T> for Combine<T> {
+    #[inline]
+    fn subscribe(&self, mut g: impl FnMut(&T) + 'static) -> StoreUnsubscriber {
+        self.store.subscribe(move |value| {
+            if let Some(value) = value {
+                g(value);
+            }
+        })
+    }
+}
+
+impl<T: 'static> Store<T> {
+    /// Derives a read-only [`Subscribable`] recomputing `f(a, b)` every time
+    /// either `a` or `b` changes.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use wasmide::prelude::*;
+    /// let first = Store::new("Ada".to_string());
+    /// let last = Store::new("Lovelace".to_string());
+    /// let full_name = Store::combine(first, last, |f, l| format!("{f} {l}"));
+    /// ```
+    pub fn combine<A: Clone + 'static, B: Clone + 'static>(
+        a: impl Subscribable<A> + 'static,
+        b: impl Subscribable<B> + 'static,
+        f: impl Fn(&A, &B) -> T + 'static,
+    ) -> Combine<T> {
+        let latest_a: Rc<RefCell<Option<A>>> = Rc::new(RefCell::new(None));
+        let latest_b: Rc<RefCell<Option<B>>> = Rc::new(RefCell::new(None));
+        let store: Store<Option<T>> = Store::new(None);
+        let f = Rc::new(f);
+
+        let recompute = {
+            let latest_a = latest_a.clone();
+            let latest_b = latest_b.clone();
+            let store = store.clone();
+            let f = f.clone();
+            move || {
+                let a_value = latest_a.borrow().clone();
+                let b_value = latest_b.borrow().clone();
+                if let (Some(a_value), Some(b_value)) = (a_value, b_value) {
+                    store.set(Some(f(&a_value, &b_value)));
+                }
+            }
+        };
+
+        let recompute_a = recompute.clone();
+        let unsub_a = a.subscribe(move |value: &A| {
+            *latest_a.borrow_mut() = Some(value.clone());
+            recompute_a();
+        });
+
+        let unsub_b = b.subscribe(move |value: &B| {
+            *latest_b.borrow_mut() = Some(value.clone());
+            recompute();
+        });
+
+        Combine {
+            store,
+            _keep_alive: Rc::new((unsub_a, unsub_b)),
+        }
+    }
+}
+
+/// The state of a value loaded asynchronously, e.g. by [`Store::from_future`]
+/// or [`fetch`].
+pub enum AsyncState<T> {
+    /// The value has not resolved yet.
+    Loading,
+    /// The value resolved successfully.
+    Ready(T),
+    /// Resolving the value failed.
+    Err(Error),
+}
+
+impl<T: 'static> Store<AsyncState<T>> {
+    /// Creates a store starting in [`AsyncState::Loading`] that transitions
+    /// to [`AsyncState::Ready`]/[`AsyncState::Err`] once `future` resolves.
+    ///
+    /// Components can render a loading spinner with `with_if` while the
+    /// store is in the `Loading` state.
+    pub fn from_future(future: impl Future<Output = Result<T>> + 'static) -> Self {
+        let store = Store::new(AsyncState::Loading);
+
+        let for_future = store.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            match future.await {
+                Ok(value) => for_future.set(AsyncState::Ready(value)),
+                Err(err) => for_future.set(AsyncState::Err(err)),
+            }
+        });
+
+        store
+    }
+}
+
+/// Fetches `url` via the browser `fetch` API, returning a store that starts
+/// in [`AsyncState::Loading`] and resolves to the response body once the
+/// request completes, the way dominator's `fetch_github` example does.
+pub fn fetch(url: &str) -> Store<AsyncState<String>> {
+    let url = url.to_string();
+
+    Store::from_future(async move {
+        let window = web_sys::window().ok_or_else(|| Error::Js("no window".to_string()))?;
+
+        let response = wasm_bindgen_futures::JsFuture::from(window.fetch_with_str(&url))
+            .await
+            .map_err(|err| Error::Js(alloc::format!("{err:?}")))?;
+        let response: web_sys::Response = wasm_bindgen::JsCast::dyn_into(response)
+            .map_err(|_| Error::Js("fetch did not resolve to a Response".to_string()))?;
+
+        let text_promise = response.text()
+            .map_err(|err| Error::Js(alloc::format!("{err:?}")))?;
+        let text = wasm_bindgen_futures::JsFuture::from(text_promise)
+            .await
+            .map_err(|err| Error::Js(alloc::format!("{err:?}")))?;
+
+        text.as_string().ok_or_else(|| Error::Js("response text() was not a string".to_string()))
+    })
+}
+
+impl<T: serde::Serialize + serde::de::DeserializeOwned + 'static> Store<T> {
+    /// Creates a store hydrated from `localStorage[key]` if present (falling
+    /// back to `default` otherwise), and writes every update back to
+    /// `localStorage` so the value survives reloads.
+    ///
+    /// The write-back subscription is owned by the store itself, so it
+    /// stays alive for as long as the store does. Serialization failures do
+    /// not panic: they are turned into a [`crate::error::Error`] that is
+    /// discarded, so the caller is not told, and the update is simply not
+    /// persisted.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use wasmide::prelude::*;
+    /// let todos: Store<Vec<String>> = Store::persistent("todos", Vec::new());
+    /// ```
+    pub fn persistent(key: &'static str, default: T) -> Self {
+        let initial = Self::read_persistent(key).unwrap_or(default);
+        let store = Store::new(initial);
+
+        let unsub = store.subscribe(move |value| {
+            // Failures come back as an `Error` rather than a panic, and the
+            // error is dropped here: a value that fails to serialize is
+            // simply not persisted.
+            let _: Result<()> = Self::write_persistent(key, value);
+        });
+        store.0.borrow_mut().keep_alive.push(unsub);
+
+        store
+    }
+
+    fn read_persistent(key: &str) -> Option<T> {
+        let raw = local_storage()?.get_item(key).ok()??;
+        serde_json::from_str(&raw).ok()
+    }
+
+    fn write_persistent(key: &str, value: &T) -> Result<()> {
+        let json = serde_json::to_string(value)
+            .map_err(|err| Error::Serde(alloc::format!("{err}")))?;
+
+        if let Some(storage) = local_storage() {
+            storage.set_item(key, &json)
+                .map_err(|_| Error::Js("localStorage.setItem failed".to_string()))?;
+        }
+
+        Ok(())
+    }
+}
+
+// Returns the window's `localStorage`, if available.
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+/// A constant [`Subscribable`] that never changes.
+///
+/// Lets plain values be passed wherever a `Subscribable` is expected, e.g.
+/// `html::p(Value("Hello, world!"), Style::NONE)`.
+pub struct Value<T>(pub T);
+
+impl<T> Subscribable<T> for Value<T> {
+    #[inline]
+    fn subscribe(&self, mut f: impl FnMut(&T) + 'static) -> StoreUnsubscriber {
+        f(&self.0);
+        StoreUnsubscriber::new(|| ())
+    }
+}
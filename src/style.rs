@@ -0,0 +1,37 @@
+//! Styling for components, expressed as a raw class-name string.
+
+/// A CSS class string applied to a [`Component`](crate::component::Component).
+///
+/// Wasmide does not interpret style strings: they are passed verbatim to
+/// `Element::set_class_name`, so any class-based styling system (Tailwind,
+/// hand-written CSS, ...) works out of the box.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Style(pub &'static str);
+
+impl Style {
+    /// The absence of any style.
+    pub const NONE: Style = Style("");
+
+    /// Interns `class` so that every [`Component`] sharing it (heavily
+    /// reused Tailwind class strings, e.g. `text-9xl`, are a common case)
+    /// only pays for crossing the JS/Rust boundary once.
+    ///
+    /// [`Component::new`](crate::component::Component::new) and
+    /// [`Component::set_style`](crate::component::Component::set_style)
+    /// already route through this, so calling it directly is only useful to
+    /// warm the cache ahead of time.
+    #[inline]
+    pub fn interned(class: &'static str) -> Style {
+        Style(intern(class))
+    }
+}
+
+// Interns `class` so that repeated calls with the same string reuse it
+// instead of re-marshalling it across the JS/Rust boundary every time.
+//
+// `wasm_bindgen::intern` already does its own content-keyed caching, so
+// there is nothing left for this crate to cache on top of it; it requires
+// the `enable-interning` feature on the `wasm-bindgen` dependency.
+pub(crate) fn intern(class: &'static str) -> &'static str {
+    wasm_bindgen::intern(class)
+}